@@ -0,0 +1,151 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::{span_lint, span_lint_and_sugg};
+use clippy_utils::source::snippet;
+use clippy_utils::sext;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::{BAD_BIT_MASK, DIV_MOD_POW_OF_TWO, INEFFECTIVE_BIT_MASK};
+
+/// Returns `Some(log2)` when `val` is a power of two (and thus `val - 1` is the mask
+/// that leaves exactly its low `log2` bits set). Shared by the ineffective
+/// bit-mask check below and by [`check_pow_of_two_div_mod`].
+fn power_of_two(val: u128) -> Option<u32> {
+    if val != 0 && val & (val - 1) == 0 {
+        Some(val.trailing_zeros())
+    } else {
+        None
+    }
+}
+
+/// Evaluates `expr` as a constant integer, sign-extending it if its type is a signed
+/// integer so that e.g. `-5i32` comes back as `-5` rather than as its bit pattern
+/// reinterpreted as a huge positive `u128`. Shared with `double_comparison`, which
+/// needs the same signed-aware evaluation for its bound math.
+pub(super) fn int_literal(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<i128> {
+    match constant(cx, cx.typeck_results(), expr)? {
+        Constant::Int(v) => match cx.typeck_results().expr_ty(expr).kind() {
+            ty::Int(ity) => Some(sext(cx.tcx, v, *ity)),
+            _ => Some(v as i128),
+        },
+        _ => None,
+    }
+}
+
+/// Picks out a `_ <bit_op> mask <cmp> c` (or the mirrored `c <cmp> _ <bit_op> mask`)
+/// shape from a comparison's two operands, returning the bit op, the mask value and
+/// the constant being compared against.
+fn match_mask_cmp(cx: &LateContext<'_>, side: &Expr<'_>, other: &Expr<'_>) -> Option<(BinOpKind, u128, u128)> {
+    let ExprKind::Binary(bit_op, _, mask_expr) = side.kind else {
+        return None;
+    };
+    if !matches!(bit_op.node, BinOpKind::And | BinOpKind::BitOr | BinOpKind::BitXor) {
+        return None;
+    }
+    let mask = int_literal(cx, mask_expr)? as u128;
+    let cmp_val = int_literal(cx, other)? as u128;
+    Some((bit_op.node, mask, cmp_val))
+}
+
+/// Mirrors a comparison so `c <cmp> x` can be read as `x <mirrored cmp> c`.
+fn mirror_cmp(op: BinOpKind) -> BinOpKind {
+    match op {
+        BinOpKind::Lt => BinOpKind::Gt,
+        BinOpKind::Le => BinOpKind::Ge,
+        BinOpKind::Gt => BinOpKind::Lt,
+        BinOpKind::Ge => BinOpKind::Le,
+        other => other,
+    }
+}
+
+/// Checks for `_ & mask <cmp> c` / `_ | mask <cmp> c` comparisons that are always
+/// `true` or always `false`, and for ones where the mask doesn't change the outcome
+/// of the comparison. See the `BAD_BIT_MASK` / `INEFFECTIVE_BIT_MASK` docs for the
+/// truth tables this implements.
+pub fn check<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>, op: BinOpKind, left: &'tcx Expr<'_>, right: &'tcx Expr<'_>) {
+    if !op.is_comparison() {
+        return;
+    }
+    let parsed = match_mask_cmp(cx, left, right)
+        .map(|parsed| (parsed, op))
+        .or_else(|| match_mask_cmp(cx, right, left).map(|parsed| (parsed, mirror_cmp(op))));
+    let Some(((bit_op, mask, cmp_val), op)) = parsed else {
+        return;
+    };
+
+    let always = match (bit_op, op) {
+        (BinOpKind::And, BinOpKind::Eq | BinOpKind::Ne) if mask & cmp_val != cmp_val => Some(op == BinOpKind::Ne),
+        (BinOpKind::And, BinOpKind::Lt | BinOpKind::Ge) if mask < cmp_val => Some(op == BinOpKind::Lt),
+        (BinOpKind::And, BinOpKind::Gt | BinOpKind::Le) if mask <= cmp_val => Some(op == BinOpKind::Le),
+        (BinOpKind::BitOr, BinOpKind::Eq | BinOpKind::Ne) if mask | cmp_val != cmp_val => Some(op == BinOpKind::Ne),
+        (BinOpKind::BitOr, BinOpKind::Lt | BinOpKind::Ge) if mask >= cmp_val => Some(op == BinOpKind::Ge),
+        (BinOpKind::BitOr, BinOpKind::Le | BinOpKind::Gt) if mask > cmp_val => Some(op == BinOpKind::Gt),
+        _ => None,
+    };
+    if let Some(always) = always {
+        span_lint(
+            cx,
+            BAD_BIT_MASK,
+            e.span,
+            &format!("this comparison involving a bit mask is always {always}"),
+        );
+        return;
+    }
+
+    let ineffective = matches!(bit_op, BinOpKind::BitOr | BinOpKind::BitXor)
+        && match op {
+            BinOpKind::Lt | BinOpKind::Ge => power_of_two(cmp_val).is_some() && mask < cmp_val,
+            BinOpKind::Le | BinOpKind::Gt => power_of_two(cmp_val.wrapping_add(1)).is_some() && mask <= cmp_val,
+            _ => false,
+        };
+    if ineffective {
+        span_lint(
+            cx,
+            INEFFECTIVE_BIT_MASK,
+            e.span,
+            "this bit mask is ineffective and can be removed without changing the outcome of the comparison",
+        );
+    }
+}
+
+/// Checks for `x % N` and `x / N` where `N` is an unsigned, compile-time
+/// power-of-two constant, suggesting the equivalent `x & (N - 1)` / `x >> log2(N)`.
+/// Guarded on the dividend's type being unsigned, since `%`/`/` on signed integers
+/// round/truncate towards zero in a way a mask or shift doesn't reproduce.
+pub fn check_pow_of_two_div_mod<'tcx>(
+    cx: &LateContext<'tcx>,
+    e: &'tcx Expr<'_>,
+    op: BinOpKind,
+    left: &'tcx Expr<'_>,
+    right: &'tcx Expr<'_>,
+) {
+    if !matches!(op, BinOpKind::Rem | BinOpKind::Div) {
+        return;
+    }
+    let ty = cx.typeck_results().expr_ty(left);
+    if !matches!(ty.kind(), ty::Uint(_)) {
+        return;
+    }
+    let Some(n) = int_literal(cx, right) else { return };
+    let n = n as u128;
+    let Some(log2) = power_of_two(n) else { return };
+
+    let lhs_snip = snippet(cx, left.span, "_");
+    let (suggestion, verb) = if op == BinOpKind::Rem {
+        (format!("{lhs_snip} & {}", n - 1), "masking the low bits")
+    } else {
+        (format!("{lhs_snip} >> {log2}", log2 = log2), "shifting right")
+    };
+
+    span_lint_and_sugg(
+        cx,
+        DIV_MOD_POW_OF_TWO,
+        e.span,
+        "this operation divides or takes the remainder with a power-of-two constant",
+        &format!("consider {verb} instead"),
+        suggestion,
+        Applicability::MachineApplicable,
+    );
+}
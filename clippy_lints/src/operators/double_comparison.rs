@@ -0,0 +1,179 @@
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::source::snippet;
+use clippy_utils::SpanlessEq;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_span::Span;
+
+use super::bit_mask::int_literal;
+use super::DOUBLE_COMPARISONS;
+
+fn flip_cmp(op: BinOpKind) -> BinOpKind {
+    match op {
+        BinOpKind::Lt => BinOpKind::Gt,
+        BinOpKind::Le => BinOpKind::Ge,
+        BinOpKind::Gt => BinOpKind::Lt,
+        BinOpKind::Ge => BinOpKind::Le,
+        other => other,
+    }
+}
+
+/// One half of a comparison against a constant, normalized so the variable would sit
+/// on the left: `Upper` is `var < c`/`var <= c`, `Lower` is `var > c`/`var >= c`.
+#[derive(Clone, Copy)]
+enum Bound {
+    Upper { incl: bool, val: i128 },
+    Lower { incl: bool, val: i128 },
+}
+
+/// Decomposes a comparison expression into the variable being compared and its
+/// normalized [`Bound`], or `None` if it isn't a `var <cmp> constant` shape (in
+/// either operand order) using one of `<`, `<=`, `>`, `>=`.
+fn bound_of<'tcx>(cx: &LateContext<'tcx>, cmp: &'tcx Expr<'_>) -> Option<(&'tcx Expr<'tcx>, Bound)> {
+    let ExprKind::Binary(op, l, r) = cmp.kind else {
+        return None;
+    };
+    let (var, val, op) = if let Some(c) = int_literal(cx, r) {
+        (l, c, op.node)
+    } else if let Some(c) = int_literal(cx, l) {
+        (r, c, flip_cmp(op.node))
+    } else {
+        return None;
+    };
+    let bound = match op {
+        BinOpKind::Lt => Bound::Upper { incl: false, val },
+        BinOpKind::Le => Bound::Upper { incl: true, val },
+        BinOpKind::Gt => Bound::Lower { incl: false, val },
+        BinOpKind::Ge => Bound::Lower { incl: true, val },
+        _ => return None,
+    };
+    Some((var, bound))
+}
+
+fn suggest_bool(cx: &LateContext<'_>, span: Span, value: bool) {
+    span_lint_and_sugg(
+        cx,
+        DOUBLE_COMPARISONS,
+        span,
+        &format!("this chain of comparisons is always {value}"),
+        "replace it with",
+        value.to_string(),
+        Applicability::MachineApplicable,
+    );
+}
+
+fn suggest_range(cx: &LateContext<'_>, span: Span, var: &Expr<'_>, lo: i128, hi: i128) {
+    let var_snip = snippet(cx, var.span, "_");
+    span_lint_and_sugg(
+        cx,
+        DOUBLE_COMPARISONS,
+        span,
+        "this chain of comparisons can be simplified to a single range check",
+        "use",
+        format!("({lo}..={hi}).contains(&{var_snip})"),
+        Applicability::MachineApplicable,
+    );
+}
+
+/// Like [`suggest_range`], but for the disjoint `x < lo || x > hi` shape, which is
+/// equivalent to the *complement* of a range check rather than a range check itself.
+fn suggest_excluded_range(cx: &LateContext<'_>, span: Span, var: &Expr<'_>, lo: i128, hi: i128) {
+    let var_snip = snippet(cx, var.span, "_");
+    span_lint_and_sugg(
+        cx,
+        DOUBLE_COMPARISONS,
+        span,
+        "this chain of comparisons can be simplified to a single range check",
+        "use",
+        format!("!({lo}..={hi}).contains(&{var_snip})"),
+        Applicability::MachineApplicable,
+    );
+}
+
+/// Handles the original `x == y || x < y` style: two comparisons of the exact same
+/// pair of operands, joined by `||`, collapsed to the one comparison operator that
+/// covers both cases (e.g. `<=`).
+fn check_same_operands<'tcx>(cx: &LateContext<'tcx>, op: BinOpKind, lhs: &'tcx Expr<'_>, rhs: &'tcx Expr<'_>, span: Span) -> bool {
+    if op != BinOpKind::Or {
+        return false;
+    }
+    let (ExprKind::Binary(lop, ll, lr), ExprKind::Binary(rop, rl, rr)) = (lhs.kind, rhs.kind) else {
+        return false;
+    };
+    let mut eq = SpanlessEq::new(cx);
+    if !(eq.eq_expr(ll, rl) && eq.eq_expr(lr, rr)) {
+        return false;
+    }
+    let combined = match (lop.node, rop.node) {
+        (BinOpKind::Eq, BinOpKind::Lt) | (BinOpKind::Lt, BinOpKind::Eq) => Some(BinOpKind::Le),
+        (BinOpKind::Eq, BinOpKind::Gt) | (BinOpKind::Gt, BinOpKind::Eq) => Some(BinOpKind::Ge),
+        (BinOpKind::Lt, BinOpKind::Gt) | (BinOpKind::Gt, BinOpKind::Lt) => Some(BinOpKind::Ne),
+        _ => None,
+    };
+    let Some(combined) = combined else { return false };
+    let l_snip = snippet(cx, ll.span, "_");
+    let r_snip = snippet(cx, lr.span, "_");
+    span_lint_and_sugg(
+        cx,
+        DOUBLE_COMPARISONS,
+        span,
+        "this comparison chain can be simplified",
+        "try",
+        format!("{l_snip} {} {r_snip}", combined.as_str()),
+        Applicability::MachineApplicable,
+    );
+    true
+}
+
+/// Handles a common variable compared against two *different* constants, e.g.
+/// `x >= 1 && x <= 5` or `x < 0 || x >= 0`, collapsing it to a single range check
+/// (or its complement, for a disjoint `||` chain like `x < 0 || x > 10`), or to a
+/// literal `true`/`false` when the two halves fully overlap/cover everything.
+fn check_const_bound_chain<'tcx>(cx: &LateContext<'tcx>, op: BinOpKind, lhs: &'tcx Expr<'_>, rhs: &'tcx Expr<'_>, span: Span) -> bool {
+    if op != BinOpKind::Or && op != BinOpKind::And {
+        return false;
+    }
+    let Some((lvar, lbound)) = bound_of(cx, lhs) else { return false };
+    let Some((rvar, rbound)) = bound_of(cx, rhs) else { return false };
+    if !SpanlessEq::new(cx).eq_expr(lvar, rvar) {
+        return false;
+    }
+
+    let (upper, lower) = match (lbound, rbound) {
+        (Bound::Upper { incl: ui, val: u }, Bound::Lower { incl: li, val: l }) => ((ui, u), (li, l)),
+        (Bound::Lower { incl: li, val: l }, Bound::Upper { incl: ui, val: u }) => ((ui, u), (li, l)),
+        _ => return false,
+    };
+    let upper_max = if upper.0 { upper.1 } else { upper.1 - 1 };
+    let lower_min = if lower.0 { lower.1 } else { lower.1 + 1 };
+
+    match op {
+        BinOpKind::Or if lower_min <= upper_max + 1 => {
+            suggest_bool(cx, span, true);
+            true
+        },
+        // The two halves no longer overlap or touch, so the disjunction excludes
+        // exactly the gap between them, e.g. `x < 0 || x > 10` excludes `0..=10`.
+        BinOpKind::Or => {
+            suggest_excluded_range(cx, span, lvar, upper_max + 1, lower_min - 1);
+            true
+        },
+        BinOpKind::And if lower_min > upper_max => {
+            suggest_bool(cx, span, false);
+            true
+        },
+        BinOpKind::And => {
+            suggest_range(cx, span, lvar, lower_min, upper_max);
+            true
+        },
+        _ => false,
+    }
+}
+
+pub fn check<'tcx>(cx: &LateContext<'tcx>, op: BinOpKind, lhs: &'tcx Expr<'_>, rhs: &'tcx Expr<'_>, span: Span) {
+    if check_same_operands(cx, op, lhs, rhs, span) {
+        return;
+    }
+    check_const_bound_chain(cx, op, lhs, rhs, span);
+}
@@ -49,13 +49,19 @@ declare_clippy_lint! {
     /// Specifically, checks for any operators (`+`, `-`, `*`, `<<`, etc) which are capable
     /// of overflowing according to the [Rust
     /// Reference](https://doc.rust-lang.org/reference/expressions/operator-expr.html#overflow),
-    /// or which can panic (`/`, `%`). No bounds analysis or sophisticated reasoning is
-    /// attempted.
+    /// or which can panic (`/`, `%`). A lightweight, conservative bounds analysis tracks
+    /// value ranges from literal assignments, bit-masks, `min`/`max`/`clamp` calls and
+    /// `if`/`else` guards, and suppresses the warning wherever that analysis can prove
+    /// the operation is in fact safe. This is deliberately best-effort: anything it can't
+    /// reason about — including `match` guards — is still flagged.
     ///
     /// ### Why is this bad?
     /// Integer overflow will trigger a panic in debug builds or will wrap in
     /// release mode. Division by zero will cause a panic in either mode. In some applications one
-    /// wants explicitly checked, wrapping or saturating arithmetic.
+    /// wants explicitly checked, wrapping or saturating arithmetic. When the `arithmetic-fix-strategy`
+    /// clippy.toml key names one of those three, this lint suggests rewriting the offending
+    /// expression to the corresponding `checked_`/`wrapping_`/`saturating_` method instead of
+    /// only warning about it.
     ///
     /// ### Example
     /// ```rust
@@ -243,10 +249,45 @@ declare_clippy_lint! {
     "expressions where a bit mask is less readable than the corresponding method call"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `x % N` and `x / N` where `N` is an unsigned, compile-time
+    /// power-of-two constant.
+    ///
+    /// ### Why is this bad?
+    /// `x % N` can be written as the equivalent `x & (N - 1)`, and `x / N` as `x >>
+    /// log2(N)`. The bitwise forms are cheaper on targets where the division/modulo
+    /// instruction isn't itself optimized into a mask or shift, and some people find
+    /// them clearer about what's actually happening to the bits.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let x: u32 = 5;
+    /// x % 16;
+    /// x / 16;
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # let x: u32 = 5;
+    /// x & 15;
+    /// x >> 4;
+    /// ```
+    #[clippy::version = "unreleased"]
+    pub DIV_MOD_POW_OF_TWO,
+    perf,
+    "using `%`/`/` on an unsigned power-of-two constant instead of `&`/`>>`"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for double comparisons that could be simplified to a single expression.
     ///
+    /// This also covers a variable compared against two different constants joined by
+    /// `||`/`&&`: adjacent or overlapping bounds collapse to a single range check, a
+    /// disjoint `||` chain (e.g. `x < 0 || x > 10`) collapses to the complement of one,
+    /// and chains that are always true or always false (e.g. `x < 0 || x >= 0`) are
+    /// flagged outright.
     ///
     /// ### Why is this bad?
     /// Readability.
@@ -256,6 +297,7 @@ declare_clippy_lint! {
     /// # let x = 1;
     /// # let y = 2;
     /// if x == y || x < y {}
+    /// if x >= 1 && x <= 5 {}
     /// ```
     ///
     /// Use instead:
@@ -264,6 +306,7 @@ declare_clippy_lint! {
     /// # let x = 1;
     /// # let y = 2;
     /// if x <= y {}
+    /// if (1..=5).contains(&x) {}
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub DOUBLE_COMPARISONS,
@@ -271,6 +314,8 @@ declare_clippy_lint! {
     "unnecessary double comparisons that can be simplified"
 }
 
+pub use numeric_arithmetic::ArithmeticFixStrategy;
+
 pub struct Operators {
     arithmetic_context: numeric_arithmetic::Context,
     verbose_bit_mask_threshold: u64,
@@ -284,18 +329,30 @@ impl_lint_pass!(Operators => [
     BAD_BIT_MASK,
     INEFFECTIVE_BIT_MASK,
     VERBOSE_BIT_MASK,
+    DIV_MOD_POW_OF_TWO,
     DOUBLE_COMPARISONS,
 ]);
 impl Operators {
     pub fn new(verbose_bit_mask_threshold: u64) -> Self {
         Self {
-            arithmetic_context: numeric_arithmetic::Context::default(),
+            arithmetic_context: numeric_arithmetic::Context::new(None),
             verbose_bit_mask_threshold,
         }
     }
+
+    /// Opts `INTEGER_ARITHMETIC` into suggesting a `checked_*`/`wrapping_*`/`saturating_*`
+    /// rewrite in place of the raw operator, per the `arithmetic-fix-strategy` clippy.toml
+    /// key. Kept as a separate builder step rather than a `new` parameter so existing
+    /// callers that only care about `verbose_bit_mask_threshold` don't need to change.
+    #[must_use]
+    pub fn with_arithmetic_fix_strategy(mut self, arithmetic_fix_strategy: Option<ArithmeticFixStrategy>) -> Self {
+        self.arithmetic_context = numeric_arithmetic::Context::new(arithmetic_fix_strategy);
+        self
+    }
 }
 impl<'tcx> LateLintPass<'tcx> for Operators {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
+        self.arithmetic_context.enter_expr(e.hir_id);
         match e.kind {
             ExprKind::Binary(op, lhs, rhs) => {
                 if !e.span.from_expansion() {
@@ -303,14 +360,17 @@ impl<'tcx> LateLintPass<'tcx> for Operators {
                 }
                 self.arithmetic_context.check_binary(cx, e, op.node, lhs, rhs);
                 bit_mask::check(cx, e, op.node, lhs, rhs);
+                bit_mask::check_pow_of_two_div_mod(cx, e, op.node, lhs, rhs);
                 verbose_bit_mask::check(cx, e, op.node, lhs, rhs, self.verbose_bit_mask_threshold);
                 double_comparison::check(cx, op.node, lhs, rhs, e.span);
             },
             ExprKind::AssignOp(op, lhs, rhs) => {
                 self.arithmetic_context.check_binary(cx, e, op.node, lhs, rhs);
+                self.arithmetic_context.check_assign(lhs);
                 misrefactored_assign_op::check(cx, e, op.node, lhs, rhs);
             },
             ExprKind::Assign(lhs, rhs, _) => {
+                self.arithmetic_context.check_assign(lhs);
                 assign_op_pattern::check(cx, e, lhs, rhs);
             },
             ExprKind::Unary(op, arg) => {
@@ -318,6 +378,15 @@ impl<'tcx> LateLintPass<'tcx> for Operators {
                     self.arithmetic_context.check_negate(cx, e, arg);
                 }
             },
+            ExprKind::If(cond, then, els) => {
+                self.arithmetic_context.enter_if(cond, then, els);
+            },
+            ExprKind::Loop(block, ..) => {
+                self.arithmetic_context.enter_loop(block);
+            },
+            ExprKind::AddrOf(_, rustc_hir::Mutability::Mut, inner) => {
+                self.arithmetic_context.check_assign(inner);
+            },
             _ => (),
         }
     }
@@ -326,6 +395,12 @@ impl<'tcx> LateLintPass<'tcx> for Operators {
         self.arithmetic_context.expr_post(e.hir_id);
     }
 
+    fn check_local(&mut self, _: &LateContext<'tcx>, local: &'tcx rustc_hir::Local<'_>) {
+        if let (rustc_hir::PatKind::Binding(_, id, ..), Some(init)) = (local.pat.kind, local.init) {
+            self.arithmetic_context.check_local(id, init);
+        }
+    }
+
     fn check_body(&mut self, cx: &LateContext<'tcx>, b: &'tcx Body<'_>) {
         self.arithmetic_context.enter_body(cx, b);
     }
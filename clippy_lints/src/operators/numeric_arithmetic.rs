@@ -0,0 +1,562 @@
+use clippy_utils::diagnostics::{span_lint, span_lint_and_sugg};
+use clippy_utils::source::snippet;
+use rustc_errors::Applicability;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::{BinOpKind, Block, Body, Expr, ExprKind, HirId, HirIdMap, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::{FLOAT_ARITHMETIC, INTEGER_ARITHMETIC};
+
+/// Which checked/wrapping/saturating family of methods, if any, `INTEGER_ARITHMETIC`
+/// should suggest in place of the raw operator. Set via the `arithmetic-fix-strategy`
+/// clippy.toml key and threaded through [`super::Operators::with_arithmetic_fix_strategy`];
+/// `None` (the default) keeps the lint warn-only, matching its behavior before this
+/// knob existed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticFixStrategy {
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+impl ArithmeticFixStrategy {
+    fn method_prefix(self) -> &'static str {
+        match self {
+            Self::Checked => "checked",
+            Self::Wrapping => "wrapping",
+            Self::Saturating => "saturating",
+        }
+    }
+
+    /// `checked_*` methods return `Option<T>` rather than `T`, so rewriting to them
+    /// changes the expression's type and isn't safe to apply without review;
+    /// `wrapping_*`/`saturating_*` both preserve `T` and are.
+    fn applicability(self) -> Applicability {
+        match self {
+            Self::Checked => Applicability::MaybeIncorrect,
+            Self::Wrapping | Self::Saturating => Applicability::MachineApplicable,
+        }
+    }
+}
+
+/// Maps a fallible binary operator to the suffix of its `checked_`/`wrapping_`/
+/// `saturating_` method for the given `strategy`, or `None` when that combination
+/// has no such method and should be left as a plain warning instead.
+///
+/// `Shl`/`Shr` are never mapped: `checked_shl`/`wrapping_shl` (and their `_shr`
+/// counterparts) require their rhs to already be `u32`, which isn't guaranteed for
+/// `a << b` where `b` is some other integer type, so suggesting them could emit code
+/// that doesn't compile. `Rem` has no `saturating_rem` (there's nothing for a
+/// remainder to saturate towards), so it's excluded only for that strategy.
+fn op_method_name(op: BinOpKind, strategy: ArithmeticFixStrategy) -> Option<&'static str> {
+    Some(match op {
+        BinOpKind::Add => "add",
+        BinOpKind::Sub => "sub",
+        BinOpKind::Mul => "mul",
+        BinOpKind::Div => "div",
+        BinOpKind::Rem if strategy != ArithmeticFixStrategy::Saturating => "rem",
+        _ => return None,
+    })
+}
+
+/// Builds the suggestion message and replacement snippet for `expr`, rewriting a
+/// `AssignOp` (`a += b`) into `a = a.wrapping_add(b)` and a plain `Binary` (`a + b`)
+/// into `a.wrapping_add(b)`.
+fn suggest_fix(
+    cx: &LateContext<'_>,
+    expr: &Expr<'_>,
+    op: BinOpKind,
+    l: &Expr<'_>,
+    r: &Expr<'_>,
+    strategy: ArithmeticFixStrategy,
+) -> Option<(String, String)> {
+    let method = op_method_name(op, strategy)?;
+    let full_method = format!("{}_{method}", strategy.method_prefix());
+    let lhs_snip = snippet(cx, l.span, "_");
+    let rhs_snip = snippet(cx, r.span, "_");
+    let replacement = match expr.kind {
+        ExprKind::AssignOp(..) => format!("{lhs_snip} = {lhs_snip}.{full_method}({rhs_snip})"),
+        _ => format!("{lhs_snip}.{full_method}({rhs_snip})"),
+    };
+    Some((format!("consider using `{full_method}`"), replacement))
+}
+
+/// A conservative `[min, max]` bound for the value a HIR local can hold at a given
+/// program point. `None` means "unknown / could be anything representable by the type",
+/// which is the safe default we fall back to whenever we can't prove anything tighter.
+#[derive(Clone, Copy, Debug)]
+struct Range {
+    min: i128,
+    max: i128,
+}
+
+impl Range {
+    fn single(v: i128) -> Self {
+        Self { min: v, max: v }
+    }
+
+    /// The full range representable by an integer of the given bit-width/signedness.
+    fn full(bits: u64, signed: bool) -> Self {
+        if signed {
+            let half = 1i128 << (bits - 1);
+            Self { min: -half, max: half - 1 }
+        } else {
+            Self { min: 0, max: (1i128 << bits) - 1 }
+        }
+    }
+
+    /// Range implied by `_ & mask`: the result can't exceed the mask and, for an
+    /// unsigned mask, can't go below zero.
+    fn from_mask(mask: i128) -> Self {
+        Self { min: 0, max: mask }
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    fn intersect_min(self, lo: i128) -> Self {
+        Self { min: self.min.max(lo), max: self.max }
+    }
+
+    fn intersect_max(self, hi: i128) -> Self {
+        Self { min: self.min, max: self.max.min(hi) }
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            min: self.min.saturating_add(other.min),
+            max: self.max.saturating_add(other.max),
+        }
+    }
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            min: self.min.saturating_sub(other.max),
+            max: self.max.saturating_sub(other.min),
+        }
+    }
+
+    /// `true` when `min > max`, i.e. the range admits no value at all. This happens
+    /// when two independently-derived constraints on the same local contradict each
+    /// other (e.g. a guard narrowing conflicting with what's already tracked), which
+    /// means the code under them is unreachable. We never use such a range to prove
+    /// anything about what actually runs; see the callers in `range_of`.
+    fn is_empty(self) -> bool {
+        self.min > self.max
+    }
+
+    fn fits_in(self, bounds: Self) -> bool {
+        !self.is_empty() && self.min >= bounds.min && self.max <= bounds.max
+    }
+
+    fn excludes_zero(self) -> bool {
+        self.min > 0 || self.max < 0
+    }
+}
+
+fn int_literal(expr: &Expr<'_>) -> Option<i128> {
+    match expr.kind {
+        ExprKind::Lit(lit) => match lit.node {
+            rustc_ast::LitKind::Int(v, _) => Some(v.get() as i128),
+            _ => None,
+        },
+        ExprKind::Unary(UnOp::Neg, inner) => int_literal(inner).map(|v| -v),
+        _ => None,
+    }
+}
+
+fn flip_cmp(op: BinOpKind) -> BinOpKind {
+    match op {
+        BinOpKind::Lt => BinOpKind::Gt,
+        BinOpKind::Le => BinOpKind::Ge,
+        BinOpKind::Gt => BinOpKind::Lt,
+        BinOpKind::Ge => BinOpKind::Le,
+        other => other,
+    }
+}
+
+fn negate_cmp(op: BinOpKind) -> BinOpKind {
+    match op {
+        BinOpKind::Lt => BinOpKind::Ge,
+        BinOpKind::Le => BinOpKind::Gt,
+        BinOpKind::Gt => BinOpKind::Le,
+        BinOpKind::Ge => BinOpKind::Lt,
+        other => other,
+    }
+}
+
+fn local_id(expr: &Expr<'_>) -> Option<HirId> {
+    if let ExprKind::Path(rustc_hir::QPath::Resolved(None, path)) = expr.kind {
+        if let rustc_hir::def::Res::Local(id) = path.res {
+            return Some(id);
+        }
+    }
+    None
+}
+
+/// A single HIR-visitor pass over a loop body that collects every local assigned
+/// anywhere inside it (through `=` or a compound `op=`). Used to invalidate those
+/// locals' ranges *before* the loop body is traversed for real, modelling the
+/// back-edge: whatever a local held going into iteration *N* may differ by the start
+/// of iteration *N+1*, so no pre-loop range can be trusted inside the loop.
+struct AssignCollector {
+    targets: Vec<HirId>,
+}
+
+impl<'tcx> Visitor<'tcx> for AssignCollector {
+    fn visit_expr(&mut self, ex: &'tcx Expr<'tcx>) {
+        if let ExprKind::Assign(lhs, ..) | ExprKind::AssignOp(_, lhs, _) = ex.kind {
+            if let Some(id) = local_id(lhs) {
+                self.targets.push(id);
+            }
+        }
+        intravisit::walk_expr(self, ex);
+    }
+}
+
+/// A [`Context`] is reset at the start of each body and tracks, per local, a
+/// conservative range derived from literal `let` bindings, bit-masks and `.min()`/
+/// `.max()`/`.clamp()` calls, narrowed for the duration of an enclosing `if`/`else`
+/// guard. Everything is wired into the real HIR traversal (via the `check_local`,
+/// `check_expr`/`check_expr_post` callbacks on [`super::Operators`]) rather than a
+/// detached pre-pass, so narrowing is only ever in effect for the branch it actually
+/// guards, and the range map is reset to "unknown" on any write we don't otherwise
+/// understand — it can only ever over-approximate overflow risk, never claim safety
+/// where there is none.
+#[derive(Default)]
+pub struct Context {
+    ranges: HirIdMap<Range>,
+    fix_strategy: Option<ArithmeticFixStrategy>,
+    /// Guard narrowings queued by `enter_if`, to be applied the instant real
+    /// traversal reaches the keyed `then`/`else` block expression.
+    pending_enter: HirIdMap<Vec<(HirId, Range)>>,
+    /// Narrowings currently in effect, stacked by the block `HirId` that applied
+    /// them, so `expr_post` can restore the exact prior value once that block ends.
+    active: Vec<(HirId, Vec<(HirId, Option<Range>)>)>,
+}
+
+impl Context {
+    pub fn new(fix_strategy: Option<ArithmeticFixStrategy>) -> Self {
+        Self {
+            fix_strategy,
+            ..Self::default()
+        }
+    }
+
+    /// Looks up the tracked range for `expr`. A stored range that's gone empty (`min
+    /// > max`) means two constraints on this local contradicted each other -- the code
+    /// reading it is unreachable -- so we report "unknown" rather than handing back a
+    /// range that could make bogus arithmetic look provably safe.
+    fn range_of(&self, expr: &Expr<'_>) -> Option<Range> {
+        match local_id(expr) {
+            Some(id) => self.ranges.get(&id).copied().filter(|r| !r.is_empty()),
+            None => int_literal(expr).map(Range::single),
+        }
+    }
+
+    /// Called from `check_local` as the real traversal reaches a `let` statement, in
+    /// program order, so later reads see exactly what's in scope at that point.
+    pub fn check_local(&mut self, pat_id: HirId, init: &Expr<'_>) {
+        if let Some(range) = self.eval_init(init) {
+            self.ranges.insert(pat_id, range);
+        } else {
+            self.ranges.remove(&pat_id);
+        }
+    }
+
+    /// Derives a range for a `let` initializer, handling the small set of shapes we
+    /// promise to understand: literals, bit-masks and `.min()`/`.max()`/`.clamp()`.
+    fn eval_init(&self, expr: &Expr<'_>) -> Option<Range> {
+        if let Some(v) = int_literal(expr) {
+            return Some(Range::single(v));
+        }
+        match expr.kind {
+            ExprKind::Binary(op, lhs, rhs) if op.node == BinOpKind::And => {
+                int_literal(rhs).or_else(|| int_literal(lhs)).map(Range::from_mask)
+            },
+            ExprKind::MethodCall(seg, recv, args, _) => {
+                let base = self.range_of(recv)?;
+                match (seg.ident.as_str(), args) {
+                    // `x.min(c)`/`x.max(c)` move both ends of the range through the
+                    // same monotonic min/max, which (unlike narrowing just one end
+                    // with `intersect_min`/`intersect_max`) can never invert it: since
+                    // `base.min <= base.max`, `f(base.min, c) <= f(base.max, c)` for
+                    // `f` = `min` or `max`.
+                    ("min", [arg]) => int_literal(arg).map(|c| Range {
+                        min: base.min.min(c),
+                        max: base.max.min(c),
+                    }),
+                    ("max", [arg]) => int_literal(arg).map(|c| Range {
+                        min: base.min.max(c),
+                        max: base.max.max(c),
+                    }),
+                    ("clamp", [lo, hi]) => {
+                        let lo = int_literal(lo)?;
+                        let hi = int_literal(hi)?;
+                        Some(Range { min: lo, max: hi })
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Invalidates whatever range we tracked for the target of an `=`/`op=` assignment
+    /// or a `&mut` borrow, since we don't attempt to model the written value precisely.
+    pub fn check_assign(&mut self, lhs: &Expr<'_>) {
+        if let Some(id) = local_id(lhs) {
+            self.forget(id);
+        }
+    }
+
+    /// Drops the tracked range for `id`, including the value saved by any narrowing
+    /// in effect, so leaving the narrowed branch doesn't bring back a stale range.
+    fn forget(&mut self, id: HirId) {
+        self.ranges.remove(&id);
+        for (_, saved) in &mut self.active {
+            for (saved_id, range) in saved.iter_mut() {
+                if *saved_id == id {
+                    *range = None;
+                }
+            }
+        }
+    }
+
+    /// Computes the bound a comparison guard implies for its single local operand,
+    /// e.g. `x < 10` implies `x`'s range is capped at `9` inside the branch it
+    /// guards (or at `>= 10` in the negated, `else`, branch). Returns `None` when the
+    /// guard isn't a `local <cmp> literal` (or flipped) shape we understand.
+    fn guard_bound(&self, cond: &Expr<'_>, negate: bool) -> Option<(HirId, Range)> {
+        // An `if` condition reaches the HIR wrapped in `DropTemps`.
+        let cond = match cond.kind {
+            ExprKind::DropTemps(inner) => inner,
+            _ => cond,
+        };
+        let ExprKind::Binary(op, lhs, rhs) = cond.kind else {
+            return None;
+        };
+        let (path_expr, lit, flipped) = if let Some(v) = int_literal(rhs) {
+            (lhs, v, false)
+        } else if let Some(v) = int_literal(lhs) {
+            (rhs, v, true)
+        } else {
+            return None;
+        };
+        let id = local_id(path_expr)?;
+
+        let mut kind = op.node;
+        if flipped {
+            kind = flip_cmp(kind);
+        }
+        if negate {
+            kind = negate_cmp(kind);
+        }
+        let base = self
+            .ranges
+            .get(&id)
+            .copied()
+            .unwrap_or(Range { min: i128::MIN, max: i128::MAX });
+        let narrowed = match kind {
+            BinOpKind::Lt => base.intersect_max(lit - 1),
+            BinOpKind::Le => base.intersect_max(lit),
+            BinOpKind::Gt => base.intersect_min(lit + 1),
+            BinOpKind::Ge => base.intersect_min(lit),
+            _ => return None,
+        };
+        Some((id, narrowed))
+    }
+
+    /// Schedules the range narrowing implied by `cond` to take effect the instant
+    /// real traversal descends into `then` (and, negated, into `els`), keyed by each
+    /// branch's own `HirId`. `enter_expr`/`expr_post` apply and unwind it exactly
+    /// when that branch is entered/left, so it can only ever affect lint decisions
+    /// made for expressions that are actually inside the guarded branch.
+    pub fn enter_if(&mut self, cond: &Expr<'_>, then: &Expr<'_>, els: Option<&Expr<'_>>) {
+        if let Some((id, range)) = self.guard_bound(cond, false) {
+            self.pending_enter.entry(then.hir_id).or_default().push((id, range));
+        }
+        if let Some(els) = els {
+            if let Some((id, range)) = self.guard_bound(cond, true) {
+                self.pending_enter.entry(els.hir_id).or_default().push((id, range));
+            }
+        }
+    }
+
+    /// Conservatively invalidates every local assigned anywhere inside a loop body
+    /// before that body is traversed for real; see [`AssignCollector`].
+    pub fn enter_loop(&mut self, block: &Block<'_>) {
+        let mut collector = AssignCollector { targets: Vec::new() };
+        collector.visit_block(block);
+        for id in collector.targets {
+            self.forget(id);
+        }
+    }
+
+    /// Called from `check_expr` for *every* expression, in traversal (pre-)order.
+    /// Applies any narrowing queued against `hir_id` by `enter_if`.
+    pub fn enter_expr(&mut self, hir_id: HirId) {
+        let Some(narrowings) = self.pending_enter.remove(&hir_id) else {
+            return;
+        };
+        let mut saved = Vec::with_capacity(narrowings.len());
+        for (id, range) in narrowings {
+            saved.push((id, self.ranges.get(&id).copied()));
+            self.ranges.insert(id, range);
+        }
+        self.active.push((hir_id, saved));
+    }
+
+    /// Called from `check_expr_post` for every expression. Restores whatever
+    /// `enter_expr` narrowed for `hir_id`, once that branch has been fully visited.
+    pub fn expr_post(&mut self, hir_id: HirId) {
+        if !matches!(self.active.last(), Some((id, _)) if *id == hir_id) {
+            return;
+        }
+        let (_, saved) = self.active.pop().unwrap();
+        for (id, range) in saved {
+            match range {
+                Some(r) => self.ranges.insert(id, r),
+                None => self.ranges.remove(&id),
+            };
+        }
+    }
+
+    pub fn check_binary<'tcx>(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        expr: &'tcx Expr<'_>,
+        op: BinOpKind,
+        l: &'tcx Expr<'_>,
+        r: &'tcx Expr<'_>,
+    ) {
+        if op.is_comparison() {
+            return;
+        }
+        // An `op=` expression itself has type `()`; its lhs has the operand type.
+        let ty = match expr.kind {
+            ExprKind::AssignOp(..) => cx.typeck_results().expr_ty(l),
+            _ => cx.typeck_results().expr_ty(expr),
+        };
+        match ty.kind() {
+            ty::Int(_) | ty::Uint(_) => {
+                if self.is_provably_safe(op, l, r, ty) {
+                    return;
+                }
+                let msg = "this arithmetic operation will overflow or panic if out of bounds";
+                if let Some(strategy) = self.fix_strategy {
+                    if let Some((sugg_msg, replacement)) = suggest_fix(cx, expr, op, l, r, strategy) {
+                        span_lint_and_sugg(
+                            cx,
+                            INTEGER_ARITHMETIC,
+                            expr.span,
+                            msg,
+                            &sugg_msg,
+                            replacement,
+                            strategy.applicability(),
+                        );
+                        return;
+                    }
+                }
+                span_lint(cx, INTEGER_ARITHMETIC, expr.span, msg);
+            },
+            ty::Float(_) => {
+                span_lint(cx, FLOAT_ARITHMETIC, expr.span, "floating-point arithmetic detected");
+            },
+            _ => (),
+        }
+    }
+
+    /// Returns `true` when the tracked ranges for `l` and `r` prove that `op` can't
+    /// overflow or (for `/`, `%`) panic for `ty`.
+    fn is_provably_safe(&self, op: BinOpKind, l: &Expr<'_>, r: &Expr<'_>, ty: rustc_middle::ty::Ty<'_>) -> bool {
+        let bits = match ty.kind() {
+            ty::Int(int_ty) => int_ty.bit_width().unwrap_or(64),
+            ty::Uint(uint_ty) => uint_ty.bit_width().unwrap_or(64),
+            _ => return false,
+        };
+        // `i128` can't represent the full range of a 128-bit integer (and shifting by
+        // its bit width to compute that range would itself overflow), so don't attempt
+        // bounds analysis for i128/u128 at all -- always fall through to the warning.
+        if bits >= 128 {
+            return false;
+        }
+        let bounds = Range::full(bits, ty.is_signed());
+
+        let Some(lr) = self.range_of(l) else { return false };
+        let Some(rr) = self.range_of(r) else { return false };
+
+        match op {
+            BinOpKind::Add => lr.add(rr).fits_in(bounds),
+            BinOpKind::Sub => lr.sub(rr).fits_in(bounds),
+            // Besides a zero divisor, signed `MIN / -1` and `MIN % -1` overflow.
+            BinOpKind::Rem | BinOpKind::Div => {
+                rr.excludes_zero() && (lr.min > bounds.min || rr.min > -1 || rr.max < -1)
+            },
+            _ => false,
+        }
+    }
+
+    pub fn check_negate<'tcx>(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, arg: &'tcx Expr<'_>) {
+        let ty = cx.typeck_results().expr_ty(expr);
+        match ty.kind() {
+            ty::Int(int_ty) => {
+                let bits = int_ty.bit_width().unwrap_or(64);
+                // Same i128-can't-represent-i128/u128's-range issue as `is_provably_safe`:
+                // skip the bounds check entirely for 128-bit types rather than building a
+                // `Range::full` that would overflow computing it.
+                if bits < 128 {
+                    if let Some(r) = self.range_of(arg) {
+                        let bounds = Range::full(bits, true);
+                        if r.min > bounds.min && -r.max >= bounds.min {
+                            return;
+                        }
+                    }
+                }
+                let msg = "this negation will overflow if the value is the type's minimum";
+                if let Some(strategy) = self.fix_strategy {
+                    let full_method = format!("{}_neg", strategy.method_prefix());
+                    let arg_snip = snippet(cx, arg.span, "_");
+                    span_lint_and_sugg(
+                        cx,
+                        INTEGER_ARITHMETIC,
+                        expr.span,
+                        msg,
+                        &format!("consider using `{full_method}`"),
+                        format!("{arg_snip}.{full_method}()"),
+                        strategy.applicability(),
+                    );
+                    return;
+                }
+                span_lint(cx, INTEGER_ARITHMETIC, expr.span, msg);
+            },
+            ty::Uint(_) => {
+                span_lint(
+                    cx,
+                    INTEGER_ARITHMETIC,
+                    expr.span,
+                    "this negation will overflow unless the value is zero",
+                );
+            },
+            ty::Float(_) => {
+                span_lint(cx, FLOAT_ARITHMETIC, expr.span, "floating-point arithmetic detected");
+            },
+            _ => (),
+        }
+    }
+
+    pub fn enter_body<'tcx>(&mut self, _cx: &LateContext<'tcx>, _body: &'tcx Body<'_>) {
+        self.ranges.clear();
+        self.pending_enter.clear();
+        self.active.clear();
+    }
+
+    pub fn body_post<'tcx>(&mut self, _cx: &LateContext<'tcx>, _body: &'tcx Body<'_>) {
+        self.ranges.clear();
+    }
+}
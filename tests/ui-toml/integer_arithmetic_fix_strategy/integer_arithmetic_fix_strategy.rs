@@ -0,0 +1,24 @@
+//@no-rustfix
+// NOTE: no compiletest/cargo uibless in this sandbox to generate a `.stderr`; see the
+// LINT markers below for the expected outcome. `clippy.toml` next to this file sets
+// arithmetic-fix-strategy = "wrapping", wired through
+// `Operators::with_arithmetic_fix_strategy`.
+#![warn(clippy::integer_arithmetic)]
+#![allow(unused)]
+
+fn main() {
+    let a: u32 = std::env::args().count() as u32;
+    let b: u32 = std::env::args().count() as u32;
+    let _ = a + b; // LINT, suggestion: a.wrapping_add(b) (MachineApplicable: same return type)
+
+    let c: i32 = std::env::args().count() as i32;
+    let _ = -c; // LINT, suggestion: c.wrapping_neg() (MachineApplicable: same return type)
+
+    // A `checked_*` suggestion (when the strategy is "checked" instead) must never be
+    // machine-applicable: `a.checked_add(b)` is `Option<u32>`, not `u32`, so applying it
+    // automatically would change the expression's type and break the surrounding code.
+    //
+    // A `saturating_*` suggestion for `%`/`<<`/`>>` must never be suggested at all:
+    // saturating_rem/saturating_shl/saturating_shr don't exist on any primitive
+    // integer type. See op_method_name in numeric_arithmetic.rs.
+}
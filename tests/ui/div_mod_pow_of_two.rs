@@ -0,0 +1,18 @@
+//@no-rustfix
+// NOTE: no compiletest/cargo uibless in this sandbox to generate a `.stderr`; see the
+// LINT/OK markers below for the expected outcome.
+#![warn(clippy::div_mod_pow_of_two)]
+#![allow(unused)]
+
+fn main() {
+    let x: u32 = 17;
+    let _ = x % 8; // LINT, suggestion: x & 7
+    let _ = x / 8; // LINT, suggestion: x >> 3
+
+    let _ = x % 7; // OK: 7 isn't a power of two
+    let _ = x / 6; // OK: 6 isn't a power of two
+
+    let y: i32 = 17;
+    let _ = y % 8; // OK: signed division/remainder round towards zero, a mask/shift isn't equivalent
+    let _ = y / 8; // OK: same reason
+}
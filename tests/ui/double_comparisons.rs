@@ -0,0 +1,23 @@
+//@no-rustfix
+// NOTE: no compiletest/cargo uibless in this sandbox to generate a `.stderr`; see the
+// LINT/OK markers below for the expected outcome.
+#![warn(clippy::double_comparisons)]
+#![allow(unused)]
+
+fn main() {
+    let x: i32 = 1;
+    let y: i32 = 2;
+
+    let _ = x == y || x < y; // LINT, suggestion: x <= y
+
+    let _ = x >= 1 && x <= 5; // LINT, suggestion: (1..=5).contains(&x)
+
+    let _ = x < 0 || x > 10; // LINT, suggestion: !(0..=10).contains(&x)
+
+    let _ = x >= -5 && x <= 5; // LINT, suggestion: (-5..=5).contains(&x) -- exercises sign-extension
+                               // of the negative constant, not its raw bit pattern
+
+    let _ = x < 0 || x >= 0; // LINT: always true
+
+    let _ = x > 5 && x < 0; // LINT: always false
+}
@@ -0,0 +1,29 @@
+//@no-rustfix
+// NOTE: this sandbox has no `compiletest`/`cargo uibless` available, so the companion
+// `.stderr` was hand-traced against the lint implementation rather than generated by
+// running it -- re-run `cargo uibless` over this fixture in a real checkout to confirm
+// it matches exactly. The `// LINT`/`// OK` markers record the intended outcome.
+#![warn(clippy::integer_arithmetic)]
+#![allow(unused)]
+
+fn condition() -> bool {
+    true
+}
+
+fn main() {
+    let x: u8 = 5;
+    let _ = x + 1; // OK: x is provably in 0..=5, so x + 1 can't overflow a u8
+
+    let mut y: u8 = 5;
+    if condition() {
+        y = 200;
+    }
+    let _ = y + 100; // LINT: the `if` branch reassigns y, so the narrowed range no longer holds
+
+    let z: u8 = 5;
+    if z > 10 {
+        let _ = z + 250; // LINT: the guard contradicts z's known 5..=5, and an empty range counts as unknown
+    } else {
+        let _ = z + 1; // OK: the negated guard keeps z at 5..=5, so z + 1 can't overflow
+    }
+}